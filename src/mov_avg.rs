@@ -0,0 +1,554 @@
+/// Running moving-average strategies sharing one interface.
+///
+/// All implementations report the current average incrementally as samples arrive, so
+/// callers can pick the averaging semantics (recency-weighted EMA variants vs. the
+/// all-history [`Cma`]) without changing call sites.
+pub trait MovingAverage {
+    fn get_result(&self) -> Option<f64>;
+    fn update(&mut self, new_value: f64);
+
+    /// Clears the accumulated state, so the instance can be reused for an independent series.
+    fn reset(&mut self);
+
+    /// Updates with `value` and returns the current smoothed value directly, so a result is
+    /// always available, even before the window fills. Unless an implementation documents
+    /// otherwise, the first sample seeds the result.
+    fn next(&mut self, value: f64) -> f64;
+}
+
+fn compute_ema(alpha: f64, prev_ema: f64, price: f64) -> f64 {
+    prev_ema + alpha * (price - prev_ema)
+}
+
+pub struct EmaFast {
+    period: usize,
+    alpha: f64,
+    result: Option<f64>,
+    last: f64,
+    count: usize,
+}
+
+impl EmaFast {
+    /// Creates a new instance with the given window `period`.
+    ///
+    /// The smoothing factor is derived as `alpha = 2 / (period + 1)`.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be greater than 0");
+        Self {
+            period,
+            alpha: 2.0 / (period + 1) as f64,
+            result: None,
+            last: 0.0,
+            count: 0,
+        }
+    }
+}
+
+impl MovingAverage for EmaFast {
+    fn get_result(&self) -> Option<f64> {
+        self.result
+    }
+
+    /// This algorithm is fast, because it executes the [`compute_ema`] function only once.
+    /// However, it does not isolate the result from the effect of values outside the window.
+    fn update(&mut self, new_price: f64) {
+        if self.count == 0 {
+            self.last = new_price;
+        } else {
+            self.last = compute_ema(self.alpha, self.last, new_price)
+        }
+        self.count += 1;
+        self.result = if self.count < self.period {
+            None
+        } else {
+            Some(self.last)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.result = None;
+        self.last = 0.0;
+        self.count = 0;
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.last
+    }
+}
+
+
+pub struct EmaCorrect {
+    period: usize,
+    alpha: f64,
+    result: Option<f64>,
+    /// first value is oldest, last is newest
+    window: Vec<f64>,
+}
+
+impl EmaCorrect {
+    /// Creates a new instance with the given window `period`.
+    ///
+    /// The smoothing factor is derived as `alpha = 2 / (period + 1)`.
+    pub fn new(period: usize) -> Self {
+        assert!(period > 0, "period must be greater than 0");
+        Self {
+            period,
+            alpha: 2.0 / (period + 1) as f64,
+            result: None,
+            window: Vec::new(),
+        }
+    }
+
+    /// Computes the EMA over whatever is currently in `window`, regardless of whether it
+    /// has reached `period` yet.
+    fn compute_from_window(&self) -> f64 {
+        let mut result = self.window[0];
+        for i in 1..self.window.len() {
+            result = compute_ema(self.alpha, result, self.window[i]);
+        }
+        result
+    }
+}
+
+impl MovingAverage for EmaCorrect {
+    fn get_result(&self) -> Option<f64> {
+        self.result
+    }
+
+    /// This algorithm performs fresh computation on the entire window, and is therefore much slower.
+    /// But it matches the logic of "window of observations" as implied by the semantics of _moving average_.
+    fn update(&mut self, new_price: f64) {
+        if self.window.len() == self.period {
+            self.window.remove(0);
+        }
+        self.window.push(new_price);
+        self.result = if self.window.len() < self.period {
+            None
+        } else {
+            Some(self.compute_from_window())
+        };
+    }
+
+    fn reset(&mut self) {
+        self.result = None;
+        self.window.clear();
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.result.unwrap_or_else(|| self.compute_from_window())
+    }
+}
+
+pub struct EmaBiasCorrected {
+    alpha: f64,
+    beta: f64,
+    m: f64,
+    t: i32,
+}
+
+impl EmaBiasCorrected {
+    /// Creates a new instance with the given window `period`.
+    ///
+    /// The smoothing factor is derived as `alpha = 2 / (period + 1)`.
+    pub fn new(period: usize) -> Self {
+        let alpha = 2.0 / (period + 1) as f64;
+        Self {
+            alpha,
+            beta: 1.0 - alpha,
+            m: 0.0,
+            t: 0,
+        }
+    }
+}
+
+impl MovingAverage for EmaBiasCorrected {
+    /// Unlike [`EmaFast`] and [`EmaCorrect`], this always reports a value once at least one
+    /// sample has been seen: the raw moment `m` is bias-corrected by dividing it by
+    /// `1 - beta^t`, which removes the downward bias caused by initializing `m` at zero.
+    fn get_result(&self) -> Option<f64> {
+        if self.t == 0 {
+            None
+        } else {
+            Some(self.m / (1.0 - self.beta.powi(self.t)))
+        }
+    }
+
+    fn update(&mut self, new_value: f64) {
+        self.m = self.beta * self.m + self.alpha * new_value;
+        self.t += 1;
+    }
+
+    fn reset(&mut self) {
+        self.m = 0.0;
+        self.t = 0;
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.get_result().expect("result is always available after at least one update")
+    }
+}
+
+/// Ehlers' "SuperSmoother": a two-pole low-pass filter that reduces lag significantly
+/// compared to a plain EMA at a given noise-filtering level.
+///
+/// https://www.mesasoftware.com/papers/PredictiveIndicators.pdf
+pub struct EmaSuperSmoother {
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    f1: f64,
+    f2: f64,
+    p1: f64,
+    count: usize,
+    result: Option<f64>,
+}
+
+impl EmaSuperSmoother {
+    /// Creates a new instance with the given `period`.
+    pub fn new(period: usize) -> Self {
+        let a1 = (-1.414 * std::f64::consts::PI / period as f64).exp();
+        let b1 = 2.0 * a1 * (1.414 * std::f64::consts::PI / period as f64).cos();
+        let c2 = b1;
+        let c3 = -a1 * a1;
+        let c1 = 1.0 - c2 - c3;
+        Self {
+            c1,
+            c2,
+            c3,
+            f1: 0.0,
+            f2: 0.0,
+            p1: 0.0,
+            count: 0,
+            result: None,
+        }
+    }
+}
+
+impl MovingAverage for EmaSuperSmoother {
+    fn get_result(&self) -> Option<f64> {
+        self.result
+    }
+
+    /// The first two samples are passed through unfiltered, since the two-pole formula needs
+    /// a previous input and two previous filter outputs to be meaningful.
+    fn update(&mut self, price: f64) {
+        let filt = if self.count < 2 {
+            price
+        } else {
+            self.c1 * (price + self.p1) / 2.0 + self.c2 * self.f1 + self.c3 * self.f2
+        };
+        self.f2 = self.f1;
+        self.f1 = filt;
+        self.p1 = price;
+        self.count += 1;
+        self.result = if self.count < 2 { None } else { Some(filt) };
+    }
+
+    fn reset(&mut self) {
+        self.f1 = 0.0;
+        self.f2 = 0.0;
+        self.p1 = 0.0;
+        self.count = 0;
+        self.result = None;
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.result.unwrap_or(value)
+    }
+}
+
+/// Cumulative Moving Average: weights every historical sample equally.
+///
+/// Unlike the EMA variants, there is no window or smoothing factor: each new value shifts
+/// the running `avg` by `(value - avg) / count`, which is an incremental form of the plain
+/// arithmetic mean over all samples seen so far.
+pub struct Cma {
+    avg: f64,
+    count: usize,
+}
+
+impl Cma {
+    pub fn new() -> Self {
+        Self { avg: 0.0, count: 0 }
+    }
+}
+
+impl Default for Cma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MovingAverage for Cma {
+    fn get_result(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.avg)
+        }
+    }
+
+    fn update(&mut self, new_value: f64) {
+        self.count += 1;
+        self.avg += (new_value - self.avg) / self.count as f64;
+    }
+
+    fn reset(&mut self) {
+        self.avg = 0.0;
+        self.count = 0;
+    }
+
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.avg
+    }
+}
+
+/// Functional-style EMA that can also [`forget`](Self::forget) part of its history, for
+/// sliding/decaying windows where observations can be retired, not just added.
+///
+/// `m` is the momentum in `[0, 1)`: the fraction of the past retained per step. `value`
+/// tracks the smoothed estimate, while `void_fraction` tracks how much of that estimate is
+/// still "void" (not backed by real samples). `get_result` withholds a value until enough
+/// real samples have pushed `void_fraction` to or below `relevance_threshold`.
+pub struct EmaRelevance {
+    m: f64,
+    relevance_threshold: f64,
+    value: f64,
+    void_fraction: f64,
+}
+
+impl EmaRelevance {
+    /// Creates a new instance with momentum `m` (fraction of the past retained per step,
+    /// in `[0, 1)`) and a `relevance_threshold` to or below which `void_fraction` must fall
+    /// for `get_result` to report a value.
+    pub fn new(m: f64, relevance_threshold: f64) -> Self {
+        Self {
+            m,
+            relevance_threshold,
+            value: 0.0,
+            void_fraction: 1.0,
+        }
+    }
+
+    /// Re-injects "voidness" to model an aging/shrinking history, without adding a new sample.
+    pub fn forget(&mut self) {
+        self.value *= self.m;
+        self.void_fraction = self.m * self.void_fraction + (1.0 - self.m);
+    }
+}
+
+impl MovingAverage for EmaRelevance {
+    fn get_result(&self) -> Option<f64> {
+        if self.void_fraction <= self.relevance_threshold {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    fn update(&mut self, new_value: f64) {
+        self.value = self.m * self.value + (1.0 - self.m) * new_value;
+        self.void_fraction *= self.m;
+    }
+
+    fn reset(&mut self) {
+        self.value = 0.0;
+        self.void_fraction = 1.0;
+    }
+
+    /// Returns `value` even while `get_result` withholds it. `value` starts at zero and is
+    /// not seeded with the first sample, so early results are pulled towards zero.
+    fn next(&mut self, value: f64) -> f64 {
+        self.update(value);
+        self.get_result().unwrap_or(self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cma, EmaBiasCorrected, EmaCorrect, EmaFast, EmaRelevance, EmaSuperSmoother, MovingAverage};
+
+    const PERIOD: usize = 5;
+
+    /// This is reused by both implementation for testing the first provided value, which is the same in both algos.
+    /// Second and further values are different, and so they will be tested separately.
+    fn basic_tests<E: MovingAverage>(ema: &mut E) {
+        for i in 1..PERIOD {
+            ema.update(i as f64);
+            assert_eq!(None, ema.get_result(), "'None' expected at position {i}");
+        }
+        ema.update(PERIOD as f64);
+        assert_eq!(Some(3.3950617283950617), ema.get_result(), "first provided value");
+    }
+
+    #[test]
+    fn fast_basic_tests() {
+        let mut ema_fast = EmaFast::new(PERIOD);
+        basic_tests(&mut ema_fast);
+        ema_fast.update(PERIOD as f64);
+        // here is the difference: following value (in first arg) is IMHO incorrect
+        assert_eq!(Some(3.9300411522633745), ema_fast.get_result(), "second provided value");
+    }
+
+    #[test]
+    fn correct_basic_tests() {
+        let mut ema_correct = EmaCorrect::new(PERIOD);
+        basic_tests(&mut ema_correct);
+        ema_correct.update(PERIOD as f64);
+        // here is the difference:
+        assert_eq!(Some(4.061728395061729), ema_correct.get_result(), "second provided value");
+    }
+
+    #[test]
+    fn compare_fast_and_correct() {
+        // fast variant
+        let secondval_fast = {
+            let mut ema_fast = EmaFast::new(PERIOD);
+            basic_tests(&mut ema_fast);
+            ema_fast.update(PERIOD as f64);
+            ema_fast.get_result()
+        };
+        // correct variant
+        let secondval_correct = {
+            let mut ema_correct = EmaCorrect::new(PERIOD);
+            basic_tests(&mut ema_correct);
+            ema_correct.update(PERIOD as f64);
+            ema_correct.get_result()
+        };
+
+        // the proof
+        assert_eq!(secondval_fast, secondval_correct, "this failure proves that the computation differs between the two algos");
+    }
+
+    #[test]
+    fn next_always_returns_a_value() {
+        let mut ema_fast = EmaFast::new(PERIOD);
+        assert_eq!(1.0, ema_fast.next(1.0), "first sample seeds the result");
+        assert_eq!(None, ema_fast.get_result(), "window not yet full");
+
+        let mut ema_correct = EmaCorrect::new(PERIOD);
+        assert_eq!(1.0, ema_correct.next(1.0), "first sample seeds the result");
+        assert_eq!(None, ema_correct.get_result(), "window not yet full");
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut ema_fast = EmaFast::new(PERIOD);
+        basic_tests(&mut ema_fast);
+        ema_fast.reset();
+        assert_eq!(None, ema_fast.get_result(), "result cleared after reset");
+        basic_tests(&mut ema_fast);
+
+        let mut ema_correct = EmaCorrect::new(PERIOD);
+        basic_tests(&mut ema_correct);
+        ema_correct.reset();
+        assert_eq!(None, ema_correct.get_result(), "result cleared after reset");
+        basic_tests(&mut ema_correct);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be greater than 0")]
+    fn fast_rejects_zero_period() {
+        EmaFast::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "period must be greater than 0")]
+    fn correct_rejects_zero_period() {
+        EmaCorrect::new(0);
+    }
+
+    #[test]
+    fn bias_corrected_reports_a_value_before_window_fills() {
+        let mut ema = EmaBiasCorrected::new(PERIOD);
+        assert_eq!(None, ema.get_result(), "nothing seen yet");
+        for i in 1..PERIOD {
+            ema.update(i as f64);
+            assert!(ema.get_result().is_some(), "bias-corrected value available at position {i}");
+        }
+    }
+
+    #[test]
+    fn bias_corrected_converges_towards_ordinary_ema() {
+        let mut ema = EmaBiasCorrected::new(PERIOD);
+        let mut ema_correct = EmaCorrect::new(PERIOD);
+        for i in 1..=PERIOD {
+            ema.update(i as f64);
+            ema_correct.update(i as f64);
+        }
+        for _ in 0..1000 {
+            ema.update(PERIOD as f64);
+            ema_correct.update(PERIOD as f64);
+        }
+        let diff = (ema.get_result().unwrap() - ema_correct.get_result().unwrap()).abs();
+        assert!(diff < 1e-9, "bias correction should vanish once t is large, diff was {diff}");
+    }
+
+    #[test]
+    fn super_smoother_passes_through_first_two_samples() {
+        let mut ema = EmaSuperSmoother::new(PERIOD);
+        assert_eq!(None, ema.get_result(), "nothing seen yet");
+        assert_eq!(1.0, ema.next(1.0), "first sample passed through");
+        assert_eq!(2.0, ema.next(2.0), "second sample passed through");
+    }
+
+    #[test]
+    fn super_smoother_filters_from_the_third_sample() {
+        let mut ema = EmaSuperSmoother::new(PERIOD);
+        ema.update(1.0);
+        ema.update(2.0);
+        ema.update(3.0);
+        // no longer a plain pass-through once the two-pole filter kicks in
+        assert_ne!(Some(3.0), ema.get_result());
+    }
+
+    #[test]
+    fn cma_averages_all_history_equally() {
+        let mut cma = Cma::new();
+        assert_eq!(None, cma.get_result(), "nothing seen yet");
+        for i in 1..=PERIOD {
+            cma.update(i as f64);
+        }
+        // mean of 1..=5
+        assert_eq!(Some(3.0), cma.get_result());
+    }
+
+    #[test]
+    fn cma_reset_clears_state() {
+        let mut cma = Cma::new();
+        for i in 1..=PERIOD {
+            cma.update(i as f64);
+        }
+        cma.reset();
+        assert_eq!(None, cma.get_result(), "result cleared after reset");
+        cma.update(10.0);
+        assert_eq!(Some(10.0), cma.get_result());
+    }
+
+    #[test]
+    fn relevance_withholds_result_until_void_fraction_drops_below_threshold() {
+        let mut ema = EmaRelevance::new(0.5, 0.3);
+        assert_eq!(None, ema.get_result(), "freshly-created estimator has no value");
+        ema.update(1.0);
+        // void_fraction is now 0.5, still above the 0.3 threshold
+        assert_eq!(None, ema.get_result());
+        ema.update(2.0);
+        // void_fraction is now 0.25, below the threshold
+        assert_eq!(Some(1.25), ema.get_result());
+    }
+
+    #[test]
+    fn relevance_forget_reinjects_voidness() {
+        let mut ema = EmaRelevance::new(0.5, 0.3);
+        ema.update(1.0);
+        ema.update(2.0);
+        assert!(ema.get_result().is_some(), "enough real samples to be relevant");
+        ema.forget();
+        assert_eq!(None, ema.get_result(), "forgetting should make the estimate void again");
+    }
+}